@@ -23,24 +23,49 @@ use core::iter::{DoubleEndedIterator, ExactSizeIterator};
 use core::mem::size_of;
 use core::ops::{Bound, RangeBounds};
 
+/// The ways a `try_range_*` call can fail on an untrusted or malformed range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitRangeError {
+    /// The range's start bit is after its end bit.
+    InvertedRange,
+    /// The range reaches past the end of the slice.
+    OutOfBounds,
+    /// The range is wider than the target type can hold.
+    WidthExceeded,
+}
+
 /// A simple bit extraction definition.
 pub trait BitRangeRead<U> {
-    // /// Reads a range of bits from the type in native endian.
-    // fn range_read_ne<R: RangeBounds<usize>>(self, range: R) -> U;
+    /// Reads a range of bits from the type in native endian.
+    fn range_read_ne<R: RangeBounds<usize>>(self, range: R) -> U;
     /// Reads a range of bits from the type in little endian.
     fn range_read_le<R: RangeBounds<usize>>(self, range: R) -> U;
     /// Reads a range of bits from the type in big endian.
     fn range_read_be<R: RangeBounds<usize>>(self, range: R) -> U;
+    /// Fallible little-endian read; see [`BitRangeError`] for why this can fail.
+    fn try_range_read_le<R: RangeBounds<usize>>(self, range: R) -> Result<U, BitRangeError>;
+    /// Fallible big-endian read; see [`BitRangeError`] for why this can fail.
+    fn try_range_read_be<R: RangeBounds<usize>>(self, range: R) -> Result<U, BitRangeError>;
 }
 
 /// A simple bit write definition.
 pub trait BitRangeWrite<U> {
-    // /// Writes a range of bits into a specific range using native endian.
-    // fn range_write_ne<R: RangeBounds<usize>>(self, range: R, value: U);
+    /// Writes a range of bits into a specific range using native endian.
+    fn range_write_ne<R: RangeBounds<usize>>(self, range: R, value: U);
     /// Writes a range of bits into a specific range using little endian.
     fn range_write_le<R: RangeBounds<usize>>(self, range: R, value: U);
     /// Writes a range of bits into a specific range using big endian.
     fn range_write_be<R: RangeBounds<usize>>(self, range: R, value: U);
+    /// Fallible little-endian write; see [`BitRangeError`] for why this can fail.
+    fn try_range_write_le<R: RangeBounds<usize>>(self, range: R, value: U) -> Result<(), BitRangeError>;
+    /// Fallible big-endian write; see [`BitRangeError`] for why this can fail.
+    fn try_range_write_be<R: RangeBounds<usize>>(self, range: R, value: U) -> Result<(), BitRangeError>;
+}
+
+/// A zero-copy, per-bit view over a range, inspired by the `bitvec` crate's `BitSlice`.
+pub trait BitRangeBits<'a> {
+    /// Returns an iterator over the individual bits of a range, least significant bit first.
+    fn range_bits_le<R: RangeBounds<usize>>(self, range: R) -> BitIter<'a>;
 }
 
 // macro_rules! impl_bit_range {
@@ -69,50 +94,228 @@ pub trait BitRangeWrite<U> {
 macro_rules! impl_bit_range_slice {
     ($($numeric:ty,)*) => {$(
         impl BitRangeRead<$numeric> for &'_ [u8] {
+            #[cfg_attr(feature = "enable-inline", inline)]
+            #[cfg_attr(feature = "never-inline", inline(never))]
+            fn range_read_ne<R: RangeBounds<usize>>(self, range: R) -> $numeric {
+                if cfg!(target_endian = "big") {
+                    self.range_read_be(range)
+                } else {
+                    self.range_read_le(range)
+                }
+            }
+
             #[cfg_attr(feature = "enable-inline", inline)]
             #[cfg_attr(feature = "never-inline", inline(never))]
             fn range_read_le<R: RangeBounds<usize>>(self, range: R) -> $numeric {
-                let res: u64 = bit_range_read_le_iter_impl(self.iter(), range);
-                res as $numeric
+                self.try_range_read_le(range).unwrap()
             }
 
             #[cfg_attr(feature = "enable-inline", inline)]
             #[cfg_attr(feature = "never-inline", inline(never))]
             fn range_read_be<R: RangeBounds<usize>>(self, range: R) -> $numeric {
-                let res: u64 = bit_range_read_le_iter_impl(self.iter().rev(), range);
-                res as $numeric
+                self.try_range_read_be(range).unwrap()
+            }
+
+            #[cfg_attr(feature = "enable-inline", inline)]
+            #[cfg_attr(feature = "never-inline", inline(never))]
+            fn try_range_read_le<R: RangeBounds<usize>>(self, range: R) -> Result<$numeric, BitRangeError> {
+                let res: u128 =
+                    bit_range_read_le_iter_impl(self.iter(), range, <$numeric>::BITS as usize)?;
+                Ok(res as $numeric)
+            }
+
+            #[cfg_attr(feature = "enable-inline", inline)]
+            #[cfg_attr(feature = "never-inline", inline(never))]
+            fn try_range_read_be<R: RangeBounds<usize>>(self, range: R) -> Result<$numeric, BitRangeError> {
+                let res: u128 =
+                    bit_range_read_le_iter_impl(self.iter().rev(), range, <$numeric>::BITS as usize)?;
+                Ok(res as $numeric)
             }
         })*
     }
 }
 
 // Slice implementations
-impl_bit_range_slice!(u8, u16, u32, u64,);
+impl_bit_range_slice!(u8, u16, u32, u64, u128,);
+
+macro_rules! impl_bit_range_read_signed_slice {
+    ($(($numeric:ty, $bits:expr),)*) => {$(
+        impl BitRangeRead<$numeric> for &'_ [u8] {
+            #[cfg_attr(feature = "enable-inline", inline)]
+            #[cfg_attr(feature = "never-inline", inline(never))]
+            fn range_read_ne<R: RangeBounds<usize>>(self, range: R) -> $numeric {
+                if cfg!(target_endian = "big") {
+                    self.range_read_be(range)
+                } else {
+                    self.range_read_le(range)
+                }
+            }
+
+            #[cfg_attr(feature = "enable-inline", inline)]
+            #[cfg_attr(feature = "never-inline", inline(never))]
+            fn range_read_le<R: RangeBounds<usize>>(self, range: R) -> $numeric {
+                self.try_range_read_le(range).unwrap()
+            }
+
+            #[cfg_attr(feature = "enable-inline", inline)]
+            #[cfg_attr(feature = "never-inline", inline(never))]
+            fn range_read_be<R: RangeBounds<usize>>(self, range: R) -> $numeric {
+                self.try_range_read_be(range).unwrap()
+            }
+
+            #[cfg_attr(feature = "enable-inline", inline)]
+            #[cfg_attr(feature = "never-inline", inline(never))]
+            fn try_range_read_le<R: RangeBounds<usize>>(self, range: R) -> Result<$numeric, BitRangeError> {
+                let res: u128 =
+                    bit_range_read_le_signed_iter_impl(self.iter(), range, $bits)?;
+                Ok(res as $numeric)
+            }
+
+            #[cfg_attr(feature = "enable-inline", inline)]
+            #[cfg_attr(feature = "never-inline", inline(never))]
+            fn try_range_read_be<R: RangeBounds<usize>>(self, range: R) -> Result<$numeric, BitRangeError> {
+                let res: u128 =
+                    bit_range_read_le_signed_iter_impl(self.iter().rev(), range, $bits)?;
+                Ok(res as $numeric)
+            }
+        })*
+    }
+}
+
+// Slice implementations
+impl_bit_range_read_signed_slice!((i8, 8), (i16, 16), (i32, 32), (i64, 64),);
 
 macro_rules! impl_bit_range_write_slice {
     ($($numeric:ty,)*) => {$(
         impl BitRangeWrite<$numeric> for &'_ mut [u8] {
+            #[cfg_attr(feature = "enable-inline", inline)]
+            #[cfg_attr(feature = "never-inline", inline(never))]
+            fn range_write_ne<R: RangeBounds<usize>>(self, range: R, value: $numeric) {
+                if cfg!(target_endian = "big") {
+                    self.range_write_be(range, value)
+                } else {
+                    self.range_write_le(range, value)
+                }
+            }
+
             #[cfg_attr(feature = "enable-inline", inline)]
             #[cfg_attr(feature = "never-inline", inline(never))]
             fn range_write_le<R: RangeBounds<usize>>(self, range: R, value: $numeric) {
-                write_le_compound(self, value as u64, range);
+                self.try_range_write_le(range, value).unwrap()
             }
 
             #[cfg_attr(feature = "enable-inline", inline)]
             #[cfg_attr(feature = "never-inline", inline(never))]
             fn range_write_be<R: RangeBounds<usize>>(self, range: R, value: $numeric) {
-                write_be_compound(self, value as u64, range);
+                self.try_range_write_be(range, value).unwrap()
+            }
+
+            #[cfg_attr(feature = "enable-inline", inline)]
+            #[cfg_attr(feature = "never-inline", inline(never))]
+            fn try_range_write_le<R: RangeBounds<usize>>(
+                self,
+                range: R,
+                value: $numeric,
+            ) -> Result<(), BitRangeError> {
+                write_le_compound(self, value as u128, range, <$numeric>::BITS as usize)
+            }
+
+            #[cfg_attr(feature = "enable-inline", inline)]
+            #[cfg_attr(feature = "never-inline", inline(never))]
+            fn try_range_write_be<R: RangeBounds<usize>>(
+                self,
+                range: R,
+                value: $numeric,
+            ) -> Result<(), BitRangeError> {
+                write_be_compound(self, value as u128, range, <$numeric>::BITS as usize)
             }
         })*
     }
 }
 
 // Slice implementations
-impl_bit_range_write_slice!(u8, u16, i32, u32, u64,);
+impl_bit_range_write_slice!(u8, u16, i32, u32, u64, u128,);
+
+impl<'a> BitRangeBits<'a> for &'a [u8] {
+    #[cfg_attr(feature = "enable-inline", inline)]
+    #[cfg_attr(feature = "never-inline", inline(never))]
+    fn range_bits_le<R: RangeBounds<usize>>(self, range: R) -> BitIter<'a> {
+        let (start_bit, total_bits, start_byte, _end_byte) =
+            setup_iter(self.len(), range, self.len() * 8).unwrap();
+        let start = start_byte * 8 + start_bit;
+
+        BitIter {
+            data: self,
+            pos: start,
+            end: start + total_bits,
+        }
+    }
+}
+
+/// A lazy, per-bit view over a range of a byte slice, produced by
+/// [`BitRangeBits::range_bits_le`]. Bits are read directly from the underlying slice as the
+/// iterator advances, so no intermediate integer is ever assembled.
+pub struct BitIter<'a> {
+    data: &'a [u8],
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> BitIter<'a> {
+    #[inline(always)]
+    fn bit_at(data: &[u8], index: usize) -> bool {
+        (data[index / 8] >> (index % 8)) & 1 != 0
+    }
+}
 
-/// Helper of common code
+impl<'a> Iterator for BitIter<'a> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<bool> {
+        if self.pos >= self.end {
+            return None;
+        }
+
+        let bit = Self::bit_at(self.data, self.pos);
+        self.pos += 1;
+        Some(bit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a> DoubleEndedIterator for BitIter<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<bool> {
+        if self.pos >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        Some(Self::bit_at(self.data, self.end))
+    }
+}
+
+impl<'a> ExactSizeIterator for BitIter<'a> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.end - self.pos
+    }
+}
+
+/// Helper of common code. Resolves `range` against a slice of length `input_len`, checking
+/// for the ways an untrusted range can be malformed; see [`BitRangeError`].
 #[inline(always)]
-fn setup_iter<'a, R>(input_len: usize, range: R) -> (usize, usize, usize, usize)
+fn setup_iter<R>(
+    input_len: usize,
+    range: R,
+    value_bits: usize,
+) -> Result<(usize, usize, usize, usize), BitRangeError>
 where
     R: RangeBounds<usize>,
 {
@@ -121,110 +324,239 @@ where
         Bound::Excluded(start) => *start + 1,
         Bound::Unbounded => 0,
     };
+    // Exclusive, unlike `start_bit`, so an empty range (e.g. `..0`) needs no underflowing
+    // subtraction to represent. Unbounded is capped at `value_bits` past `start_bit` (rather
+    // than the full slice) so that e.g. `range_read_le::<u8>(..)` on a slice wider than a
+    // byte reads the low `value_bits` bits of the field instead of tripping the width check
+    // below.
     let end_bit = match range.end_bound() {
-        Bound::Included(end) => *end,
-        Bound::Excluded(end) => *end - 1,
-        Bound::Unbounded => size_of::<u64>() * 8 - 1,
+        Bound::Included(end) => end.checked_add(1).ok_or(BitRangeError::OutOfBounds)?,
+        Bound::Excluded(end) => *end,
+        Bound::Unbounded => (input_len * 8).min(start_bit.saturating_add(value_bits)),
     };
-    let total_bits = end_bit - start_bit + 1;
-    let start_byte = start_bit / 8;
-    let end_byte = (end_bit / 8).min(input_len);
 
-    let start_bit = start_bit - start_byte * 8;
+    if end_bit < start_bit {
+        return Err(BitRangeError::InvertedRange);
+    }
+
+    let total_bits = end_bit - start_bit;
+    let needed_bytes = end_bit.div_ceil(8);
+    if needed_bytes > input_len {
+        return Err(BitRangeError::OutOfBounds);
+    }
+
+    let start_byte = start_bit / 8;
+    let end_byte = end_bit.saturating_sub(1) / 8;
 
-    (start_bit, total_bits, start_byte, end_byte)
+    Ok((start_bit - start_byte * 8, total_bits, start_byte, end_byte))
 }
 
+#[cfg_attr(feature = "enable-inline", inline)]
+#[cfg_attr(feature = "never-inline", inline(never))]
+fn bit_range_read_le_iter_impl<'a, I, R>(
+    input: I,
+    range: R,
+    value_bits: usize,
+) -> Result<u128, BitRangeError>
+where
+    I: Iterator<Item = &'a u8> + DoubleEndedIterator + ExactSizeIterator,
+    R: RangeBounds<usize>,
+{
+    let (start_bit, total_bits, start_byte, end_byte) =
+        setup_iter(input.len(), range, value_bits)?;
+    if total_bits > value_bits {
+        return Err(BitRangeError::WidthExceeded);
+    }
+
+    let iter = input.skip(start_byte).take(end_byte + 1);
+
+    let buf = read_work_bytes(iter);
+    let mut output = work_bytes_to_u128(buf, start_bit);
+    output &= bits_mask(total_bits);
+
+    Ok(output)
+}
 
 #[cfg_attr(feature = "enable-inline", inline)]
 #[cfg_attr(feature = "never-inline", inline(never))]
-fn bit_range_read_le_iter_impl<'a, I, R>(input: I, range: R) -> u64
+fn bit_range_read_le_signed_iter_impl<'a, I, R>(
+    input: I,
+    range: R,
+    value_bits: usize,
+) -> Result<u128, BitRangeError>
 where
     I: Iterator<Item = &'a u8> + DoubleEndedIterator + ExactSizeIterator,
     R: RangeBounds<usize>,
 {
-    let (start_bit, total_bits, start_byte, end_byte) = setup_iter(input.len(), range);
+    let (start_bit, total_bits, start_byte, end_byte) =
+        setup_iter(input.len(), range, value_bits)?;
+    if total_bits > value_bits {
+        return Err(BitRangeError::WidthExceeded);
+    }
 
     let iter = input.skip(start_byte).take(end_byte + 1);
 
-    // The rust compiler is smart enough to see through this and not so u128 operations.
-    let mask = (1 << total_bits) - 1;
-    let mut output = read_u128_le(iter);
-    output >>= start_bit;
+    let buf = read_work_bytes(iter);
+    let mut output = work_bytes_to_u128(buf, start_bit);
+    let mask = bits_mask(total_bits);
     output &= mask;
 
-    output as u64
+    // Sign-extend the `total_bits`-wide field up through the target width, unless it is
+    // already full width (nothing to extend) or zero-width (nothing to extend with).
+    if total_bits != 0 && total_bits < value_bits {
+        let sign_bit = (output >> (total_bits - 1)) & 1;
+        if sign_bit == 1 {
+            output |= !mask;
+        }
+    }
+
+    Ok(output)
 }
 
 #[cfg_attr(feature = "enable-inline", inline)]
 #[cfg_attr(feature = "never-inline", inline(never))]
-fn write_le_compound<R>(output: &mut [u8], val: u64, range: R)
+fn write_le_compound<R>(
+    output: &mut [u8],
+    val: u128,
+    range: R,
+    value_bits: usize,
+) -> Result<(), BitRangeError>
 where
     R: RangeBounds<usize>,
 {
-    let (start_bit, total_bits, start_byte, end_byte) = setup_iter(output.len(), range);
-    let iter = output.iter().skip(start_byte).take(end_byte + 1);
-
-    // Extract area as u128
-    let mut work_value = read_u128_le(iter);
+    let (start_bit, total_bits, start_byte, end_byte) =
+        setup_iter(output.len(), range, value_bits)?;
+    if total_bits > value_bits {
+        return Err(BitRangeError::WidthExceeded);
+    }
 
-    let mask = ((1 << total_bits) - 1) << start_bit;
-    let val = ((val as u128) << start_bit) & mask;
+    let iter = output.iter().skip(start_byte).take(end_byte + 1);
 
-    // Modify area
-    work_value &= !mask;
-    work_value |= val;
+    let buf = read_work_bytes(iter);
+    let buf = merge_work_bytes(buf, val, start_bit, total_bits);
 
     let iter = output.iter_mut().skip(start_byte).take(end_byte + 1);
 
     // Write area back
-    write_value_le(iter, work_value);
+    write_work_bytes(iter, buf);
+
+    Ok(())
 }
 
 #[cfg_attr(feature = "enable-inline", inline)]
 #[cfg_attr(feature = "never-inline", inline(never))]
-fn write_be_compound<R>(output: &mut [u8], val: u64, range: R)
+fn write_be_compound<R>(
+    output: &mut [u8],
+    val: u128,
+    range: R,
+    value_bits: usize,
+) -> Result<(), BitRangeError>
 where
     R: RangeBounds<usize>,
 {
-    let (start_bit, total_bits, start_byte, end_byte) = setup_iter(output.len(), range);
-    let iter = output.iter().rev().skip(start_byte).take(end_byte + 1);
-
-    // Extract area as u128
-    let mut work_value = read_u128_le(iter);
+    let (start_bit, total_bits, start_byte, end_byte) =
+        setup_iter(output.len(), range, value_bits)?;
+    if total_bits > value_bits {
+        return Err(BitRangeError::WidthExceeded);
+    }
 
-    let mask = ((1 << total_bits) - 1) << start_bit;
-    let val = ((val as u128) << start_bit) & mask;
+    let iter = output.iter().rev().skip(start_byte).take(end_byte + 1);
 
-    // Modify area
-    work_value &= !mask;
-    work_value |= val;
+    let buf = read_work_bytes(iter);
+    let buf = merge_work_bytes(buf, val, start_bit, total_bits);
 
     let iter = output.iter_mut().rev().skip(start_byte).take(end_byte + 1);
 
     // Write area back
-    write_value_le(iter, work_value);
+    write_work_bytes(iter, buf);
+
+    Ok(())
 }
 
+/// Number of bytes in the widened work buffer: a full `u128` plus one extra byte that a
+/// non-zero `start_bit` can pull into the window (a field can span up to 128 bits starting
+/// at a non-zero bit offset, so 16 bytes alone are not always enough).
+const WORK_BYTES: usize = size_of::<u128>() + 1;
+
+/// Loads up to `WORK_BYTES` bytes from `input` into a zero-padded buffer, byte `i` of the
+/// buffer holding byte `i` of `input`.
 #[inline(always)]
-fn read_u128_le<'a, I>(input: I) -> u128
+fn read_work_bytes<'a, I>(input: I) -> [u8; WORK_BYTES]
 where
-    I: Iterator<Item = &'a u8> + DoubleEndedIterator,
+    I: Iterator<Item = &'a u8>,
 {
-    input.rev().fold(0, |acc, x| (acc << 8) | *x as u128)
+    let mut buf = [0u8; WORK_BYTES];
+    for (slot, byte) in buf.iter_mut().zip(input) {
+        *slot = *byte;
+    }
+    buf
 }
 
+/// Writes `buf` back out through `output`, one byte per item, in the same order it was read.
 #[inline(always)]
-fn write_value_le<'a, O>(output: O, value: u128)
+fn write_work_bytes<'a, O>(output: O, buf: [u8; WORK_BYTES])
 where
-    O: Iterator<Item = &'a mut u8> + DoubleEndedIterator,
+    O: Iterator<Item = &'a mut u8>,
 {
-    let val_as_bytes = &value.to_be_bytes();
-    val_as_bytes
-        .iter()
-        .rev()
-        .zip(output)
-        .for_each(|(i, o)| *o = *i);
+    for (slot, byte) in output.zip(buf.iter()) {
+        *slot = *byte;
+    }
+}
+
+/// Reassembles a work buffer into a `u128`, shifting right by `start_bit` across the extra
+/// byte so a field spanning all `WORK_BYTES` bytes is not truncated.
+#[inline(always)]
+fn work_bytes_to_u128(buf: [u8; WORK_BYTES], start_bit: usize) -> u128 {
+    let low = u128::from_le_bytes(buf[..size_of::<u128>()].try_into().unwrap());
+    if start_bit == 0 {
+        low
+    } else {
+        (low >> start_bit) | ((buf[WORK_BYTES - 1] as u128) << (128 - start_bit))
+    }
+}
+
+/// The `total_bits`-wide all-ones mask, handling `total_bits >= 128` where `1u128 << 128`
+/// would overflow.
+#[inline(always)]
+fn bits_mask(total_bits: usize) -> u128 {
+    if total_bits >= 128 {
+        u128::MAX
+    } else {
+        (1u128 << total_bits) - 1
+    }
+}
+
+/// Merges `val`'s low `total_bits` bits into `buf` at bit offset `start_bit`, across the
+/// extra byte if the field spans all `WORK_BYTES` bytes.
+#[inline(always)]
+fn merge_work_bytes(
+    buf: [u8; WORK_BYTES],
+    val: u128,
+    start_bit: usize,
+    total_bits: usize,
+) -> [u8; WORK_BYTES] {
+    let mask = bits_mask(total_bits);
+    let val = val & mask;
+
+    let (low_val, extra_val) = if start_bit == 0 {
+        (val, 0u8)
+    } else {
+        (val << start_bit, (val >> (128 - start_bit)) as u8)
+    };
+    let (low_mask, extra_mask) = if start_bit == 0 {
+        (mask, 0u8)
+    } else {
+        (mask << start_bit, (mask >> (128 - start_bit)) as u8)
+    };
+
+    let low = u128::from_le_bytes(buf[..size_of::<u128>()].try_into().unwrap());
+    let new_low = (low & !low_mask) | low_val;
+    let new_extra = (buf[WORK_BYTES - 1] & !extra_mask) | extra_val;
+
+    let mut out = [0u8; WORK_BYTES];
+    out[..size_of::<u128>()].copy_from_slice(&new_low.to_le_bytes());
+    out[WORK_BYTES - 1] = new_extra;
+    out
 }
 
 #[cfg(test)]
@@ -77,3 +77,156 @@ fn read_range_le() {
     println!("r: {:x}", r);
     assert_eq!(r, 0xd7a6_751e_3e6e_9063);
 }
+
+#[test]
+fn read_u128_full_width() {
+    let val: u128 = 0xdead_beef_0011_2233_4455_6677_8899_aabb;
+    let bytes = &val.to_le_bytes();
+    let r: u128 = bytes.range_read_le(..);
+    assert_eq!(r, val);
+}
+
+#[test]
+fn read_write_u128_misaligned() {
+    // A full 128-bit field starting at a non-zero bit offset spans 17 bytes, one more than
+    // `u128` itself can hold.
+    let y = &mut [0u8; 17];
+    let val: u128 = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+
+    y.range_write_le(4..132, val);
+    let back: u128 = (&y[..]).range_read_le(4..132);
+
+    assert_eq!(back, val);
+}
+
+#[test]
+fn read_signed_sign_extension() {
+    let y = &mut [0u8; 2];
+
+    y.range_write_le(0..4, -1i32);
+    let r: i8 = (&y[..]).range_read_le(0..4);
+    assert_eq!(r, -1);
+
+    y.range_write_le(0..4, 0b0111i32);
+    let r: i8 = (&y[..]).range_read_le(0..4);
+    assert_eq!(r, 0b0111);
+
+    // Full-width read must not over-extend.
+    y.range_write_le(0..8, -2i32);
+    let r: i8 = (&y[..]).range_read_le(0..8);
+    assert_eq!(r, -2);
+}
+
+#[test]
+fn bit_iter_matches_range_read() {
+    let y = &[0b1101_0011u8, 0b0110_1001];
+
+    let bits: std::vec::Vec<bool> = (&y[..]).range_bits_le(2..13).collect();
+    let expected: u32 = y.range_read_le(2..13);
+
+    for (i, bit) in bits.iter().enumerate() {
+        assert_eq!(*bit, (expected >> i) & 1 != 0);
+    }
+
+    // DoubleEndedIterator and ExactSizeIterator both agree with the forward count.
+    let mut iter = (&y[..]).range_bits_le(2..13);
+    assert_eq!(iter.len(), 11);
+    assert_eq!(iter.next_back(), Some(bits[10]));
+    assert_eq!(iter.len(), 10);
+}
+
+#[test]
+fn try_read_valid_range_matches_infallible() {
+    let y = &[0x0eu8, 0xd3, 0xf1, 0x8f];
+
+    let expected: u32 = y.range_read_le(8..24);
+    assert_eq!(y.try_range_read_le(8..24), Ok(expected));
+}
+
+#[test]
+fn try_read_out_of_bounds() {
+    let y = &[0x0eu8, 0xd3];
+
+    let r: Result<u32, BitRangeError> = y.try_range_read_le(0..32);
+    assert_eq!(r, Err(BitRangeError::OutOfBounds));
+}
+
+#[test]
+fn try_read_inverted_range() {
+    use core::ops::Bound;
+
+    let y = &[0x0eu8, 0xd3];
+
+    let r: Result<u32, BitRangeError> =
+        y.try_range_read_le((Bound::Included(12), Bound::Excluded(4)));
+    assert_eq!(r, Err(BitRangeError::InvertedRange));
+}
+
+#[test]
+fn try_read_width_exceeded() {
+    let y = &[0x0eu8, 0xd3, 0xf1, 0x8f, 0xff];
+
+    let r: Result<u8, BitRangeError> = y.try_range_read_le(0..16);
+    assert_eq!(r, Err(BitRangeError::WidthExceeded));
+}
+
+#[test]
+fn try_read_empty_range_does_not_panic() {
+    let y = &[0x0eu8, 0xd3];
+
+    let r: u32 = y.try_range_read_le(..0).unwrap();
+    assert_eq!(r, 0);
+}
+
+#[test]
+fn unbounded_read_on_wider_slice_keeps_low_bits() {
+    // `..` on a slice wider than the target type must read the low `value_bits` bits rather
+    // than erroring, matching the infallible methods' promise of preserving prior behavior.
+    let y = &[0xaau8, 0xbb, 0xcc, 0xdd];
+
+    let r: u8 = y.range_read_le(..);
+    assert_eq!(r, 0xaa);
+
+    let r: u32 = [0x63u8, 0x90, 0x6e, 0x3e, 0x1e, 0x75, 0xa6, 0xd7].range_read_le(..);
+    assert_eq!(r, 0x3e6e_9063);
+
+    // Same cap applies to the BE read path and to both write paths. BE treats the last
+    // byte of the slice as the low byte, so the capped read picks that one up.
+    let r: u8 = y.range_read_be(..);
+    assert_eq!(r, 0xdd);
+
+    let out = &mut [0u8; 4];
+    out.range_write_le(.., 0xaau8);
+    assert_eq!(out, &[0xaa, 0, 0, 0]);
+
+    let out = &mut [0u8; 4];
+    out.range_write_be(.., 0xaau8);
+    assert_eq!(out, &[0, 0, 0, 0xaa]);
+}
+
+#[test]
+fn native_endian_matches_platform_endian() {
+    let y_arr = &[0b00001010u8, 0b01010000, 0b11110000, 0b00001111];
+
+    let ne: u32 = y_arr.range_read_ne(..);
+    let expected: u32 = if cfg!(target_endian = "big") {
+        y_arr.range_read_be(..)
+    } else {
+        y_arr.range_read_le(..)
+    };
+    assert_eq!(ne, expected);
+
+    let mut out = [0u8; 4];
+    (&mut out[..]).range_write_ne(.., ne);
+    assert_eq!(&out, y_arr);
+}
+
+#[test]
+fn try_write_out_of_bounds() {
+    let y = &mut [0u8; 2];
+
+    assert_eq!(
+        y.try_range_write_le(0..32, 0u32),
+        Err(BitRangeError::OutOfBounds)
+    );
+}